@@ -1,5 +1,6 @@
+use async_trait::async_trait;
 use axum::{
-    extract::State,
+    extract::{Query, State},
     http::StatusCode,
     response::{IntoResponse, Json},
     routing::get,
@@ -14,10 +15,11 @@ use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 use tracing::info;
 
-// Matches the Move struct: PriceUpdatePayload { price: u64 }
+// Matches the Move struct: PriceUpdatePayload { price: u64, nonce: u64 }
 #[derive(Serialize, Deserialize)]
 struct PriceUpdatePayload {
     price: u64,
+    nonce: u64,
 }
 
 // Matches Nautilus IntentMessage structure
@@ -33,7 +35,86 @@ struct IntentMessage<T> {
 struct SignedPriceResponse {
     price: u64,
     timestamp_ms: u64,
-    signature: String, // hex-encoded
+    nonce: u64,
+    signature: String, // hex-encoded; 64 bytes (r||s), or 65 bytes (r||s||v) if recoverable
+    #[serde(skip_serializing_if = "Option::is_none")]
+    recovery_id: Option<i32>,
+    hash_scheme: HashScheme,
+}
+
+/// Prehash function used before signing. Sui's `ecdsa_k1` verifier expects
+/// SHA-256 with hash flag 1; EVM-style verifiers expect Keccak-256.
+#[derive(Clone, Copy, Serialize, Debug)]
+#[serde(rename_all = "lowercase")]
+enum HashScheme {
+    Sha256,
+    Keccak256,
+}
+
+impl std::str::FromStr for HashScheme {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "sha256" => Ok(HashScheme::Sha256),
+            "keccak256" => Ok(HashScheme::Keccak256),
+            other => anyhow::bail!("Unknown hash scheme '{}', expected sha256 or keccak256", other),
+        }
+    }
+}
+
+impl HashScheme {
+    fn digest(&self, bytes: &[u8]) -> [u8; 32] {
+        match self {
+            HashScheme::Sha256 => {
+                use sha2::Digest;
+                sha2::Sha256::digest(bytes).into()
+            }
+            HashScheme::Keccak256 => {
+                use tiny_keccak::{Hasher, Keccak};
+                let mut hasher = Keccak::v256();
+                let mut output = [0u8; 32];
+                hasher.update(bytes);
+                hasher.finalize(&mut output);
+                output
+            }
+        }
+    }
+}
+
+// Query params accepted by `GET /price`
+#[derive(Deserialize, Default)]
+struct GetPriceParams {
+    #[serde(default)]
+    recoverable: bool,
+}
+
+/// Resolves the `X-Api-Key` header against `state.api_keys` to get the
+/// caller id to bill.
+fn authenticate_caller(
+    state: &AppState,
+    headers: &axum::http::HeaderMap,
+) -> Result<String, (StatusCode, String)> {
+    let api_key = headers
+        .get("x-api-key")
+        .and_then(|v| v.to_str().ok())
+        .ok_or((StatusCode::UNAUTHORIZED, "Missing X-Api-Key header".to_string()))?;
+
+    state
+        .api_keys
+        .get(api_key)
+        .cloned()
+        .ok_or((StatusCode::UNAUTHORIZED, "Unknown API key".to_string()))
+}
+
+// Response format for `GET /bill`
+#[derive(Serialize)]
+struct BillResponse {
+    costs: std::collections::HashMap<String, u64>,
+    hash: String, // hex-encoded, finalized snapshot of the running billing hash
+    signature: String,
+    recovery_id: i32,
+    hash_scheme: HashScheme,
 }
 
 // CoinGecko API response structure
@@ -47,27 +128,447 @@ struct CoinGeckoPrice {
     usd: f64,
 }
 
+// Binance ticker REST response structure
+#[derive(Deserialize, Debug)]
+struct BinanceTickerResponse {
+    price: String,
+}
+
+// A single upstream feed that can report the latest SUI price.
+//
+// Implementors should fail fast (network error, bad payload) rather than
+// return a stale or placeholder value; `AggregatedSource` treats an `Err`
+// the same as a dropped feed. `latest_price` takes the aggregator's
+// configured `max_staleness_ms` so a cache-backed source (e.g.
+// `KrakenSource`) checks its reads against the same threshold as the rest
+// of the request.
+#[async_trait]
+trait PriceSource: Send + Sync {
+    async fn latest_price(&self, max_staleness_ms: u64) -> Result<f64, anyhow::Error>;
+
+    /// Human-readable name used in logs when a feed is dropped.
+    fn name(&self) -> &'static str;
+}
+
+struct CoinGeckoSource {
+    http_client: reqwest::Client,
+}
+
+#[async_trait]
+impl PriceSource for CoinGeckoSource {
+    async fn latest_price(&self, _max_staleness_ms: u64) -> Result<f64, anyhow::Error> {
+        let url = "https://api.coingecko.com/api/v3/simple/price?ids=sui&vs_currencies=usd";
+
+        let response = self
+            .http_client
+            .get(url)
+            .send()
+            .await?
+            .json::<CoinGeckoResponse>()
+            .await?;
+
+        Ok(response.sui.usd)
+    }
+
+    fn name(&self) -> &'static str {
+        "coingecko"
+    }
+}
+
+// (price, timestamp_ms) of the most recent Kraken ticker update, kept fresh
+// by `run_kraken_ticker_task` so reads never block on the network.
+type KrakenTickerCache = Arc<tokio::sync::RwLock<Option<(f64, u64)>>>;
+
+struct KrakenSource {
+    cache: KrakenTickerCache,
+}
+
+#[async_trait]
+impl PriceSource for KrakenSource {
+    async fn latest_price(&self, max_staleness_ms: u64) -> Result<f64, anyhow::Error> {
+        let cached = *self.cache.read().await;
+        let (price, updated_at_ms) =
+            cached.ok_or_else(|| anyhow::anyhow!("Kraken ticker cache not yet populated"))?;
+
+        let age_ms = current_timestamp_ms().saturating_sub(updated_at_ms);
+        if age_ms > max_staleness_ms {
+            anyhow::bail!("Kraken ticker cache is stale ({}ms old)", age_ms);
+        }
+
+        Ok(price)
+    }
+
+    fn name(&self) -> &'static str {
+        "kraken"
+    }
+}
+
+/// Background task that keeps `cache` up to date with Kraken's websocket
+/// ticker feed, reconnecting with exponential backoff on any error.
+async fn run_kraken_ticker_task(cache: KrakenTickerCache) {
+    use futures::{SinkExt, StreamExt};
+    use tokio_tungstenite::tungstenite::Message as WsMessage;
+
+    const KRAKEN_WS_URL: &str = "wss://ws.kraken.com";
+    const INITIAL_BACKOFF_MS: u64 = 500;
+    const MAX_BACKOFF_MS: u64 = 30_000;
+
+    let mut backoff_ms = INITIAL_BACKOFF_MS;
+
+    loop {
+        match tokio_tungstenite::connect_async(KRAKEN_WS_URL).await {
+            Ok((mut ws_stream, _)) => {
+                info!("Connected to Kraken ticker websocket");
+                backoff_ms = INITIAL_BACKOFF_MS;
+
+                let subscribe = serde_json::json!({
+                    "event": "subscribe",
+                    "pair": ["SUI/USD"],
+                    "subscription": { "name": "ticker" }
+                });
+                match ws_stream.send(WsMessage::Text(subscribe.to_string())).await {
+                    Ok(()) => {
+                        while let Some(msg) = ws_stream.next().await {
+                            let msg = match msg {
+                                Ok(msg) => msg,
+                                Err(e) => {
+                                    tracing::warn!("Kraken websocket error: {}", e);
+                                    break;
+                                }
+                            };
+
+                            let WsMessage::Text(text) = msg else {
+                                continue;
+                            };
+
+                            let Ok(value) = serde_json::from_str::<serde_json::Value>(&text) else {
+                                continue;
+                            };
+
+                            // Event objects (heartbeat, systemStatus, subscriptionStatus) are
+                            // JSON objects; ticker payloads are arrays, so this also filters
+                            // out everything that isn't a price update.
+                            if value.is_object() {
+                                continue;
+                            }
+
+                            let Some(price_str) = value
+                                .get(1)
+                                .and_then(|ticker| ticker.get("c"))
+                                .and_then(|c| c.get(0))
+                                .and_then(|c| c.as_str())
+                            else {
+                                continue;
+                            };
+
+                            match price_str.parse::<f64>() {
+                                Ok(price) => {
+                                    *cache.write().await = Some((price, current_timestamp_ms()));
+                                }
+                                Err(e) => {
+                                    tracing::warn!("Failed to parse Kraken ticker price: {}", e);
+                                }
+                            }
+                        }
+
+                        tracing::warn!("Kraken websocket stream ended, reconnecting");
+                    }
+                    Err(e) => {
+                        tracing::warn!("Failed to send Kraken subscribe frame: {}", e);
+                    }
+                }
+            }
+            Err(e) => {
+                tracing::warn!("Failed to connect to Kraken websocket: {}", e);
+            }
+        }
+
+        tokio::time::sleep(std::time::Duration::from_millis(backoff_ms)).await;
+        backoff_ms = (backoff_ms * 2).min(MAX_BACKOFF_MS);
+    }
+}
+
+struct BinanceSource {
+    http_client: reqwest::Client,
+}
+
+#[async_trait]
+impl PriceSource for BinanceSource {
+    async fn latest_price(&self, _max_staleness_ms: u64) -> Result<f64, anyhow::Error> {
+        let url = "https://api.binance.com/api/v3/ticker/price?symbol=SUIUSDT";
+
+        let response = self
+            .http_client
+            .get(url)
+            .send()
+            .await?
+            .json::<BinanceTickerResponse>()
+            .await?;
+
+        Ok(response.price.parse::<f64>()?)
+    }
+
+    fn name(&self) -> &'static str {
+        "binance"
+    }
+}
+
+/// Queries every configured `PriceSource` concurrently and returns the median
+/// of the surviving responses.
+struct AggregatedSource {
+    sources: Vec<Box<dyn PriceSource>>,
+    quorum: usize,
+    max_staleness_ms: u64,
+}
+
+impl AggregatedSource {
+    fn new(sources: Vec<Box<dyn PriceSource>>, quorum: usize, max_staleness_ms: u64) -> Self {
+        Self {
+            sources,
+            quorum,
+            max_staleness_ms,
+        }
+    }
+
+    /// Fetches from every source, drops errors and values older than
+    /// `max_staleness_ms` (per-source, per the `PriceSource` contract), and
+    /// returns the median of whatever is left (erroring below `quorum`
+    /// survivors).
+    async fn median_price(&self) -> Result<f64, anyhow::Error> {
+        let results = futures::future::join_all(self.sources.iter().map(|source| async move {
+            let price = source.latest_price(self.max_staleness_ms).await;
+            (source.name(), price)
+        }))
+        .await;
+
+        let mut prices: Vec<f64> = Vec::with_capacity(results.len());
+        for (name, result) in results {
+            match result {
+                Ok(price) if price.is_nan() => {
+                    tracing::warn!("Dropping price feed '{}': returned NaN", name);
+                }
+                Ok(price) => prices.push(price),
+                Err(e) => {
+                    tracing::warn!("Dropping price feed '{}': {}", name, e);
+                }
+            }
+        }
+
+        if prices.len() < self.quorum {
+            anyhow::bail!(
+                "Only {} of {} price feeds responded, need at least {}",
+                prices.len(),
+                self.sources.len(),
+                self.quorum
+            );
+        }
+
+        prices.sort_by(|a, b| a.partial_cmp(b).expect("NaN values are filtered out above"));
+
+        let mid = prices.len() / 2;
+        let median = if prices.len() % 2 == 0 {
+            (prices[mid - 1] + prices[mid]) / 2.0
+        } else {
+            prices[mid]
+        };
+
+        Ok(median)
+    }
+}
+
+// Incremental hasher matching `HashScheme`, so `Billing` can fold each
+// request into a fixed-size running digest instead of buffering history.
+enum RunningHash {
+    Sha256(sha2::Sha256),
+    Keccak256(tiny_keccak::Keccak),
+}
+
+impl RunningHash {
+    fn new(scheme: HashScheme) -> Self {
+        match scheme {
+            HashScheme::Sha256 => RunningHash::Sha256(sha2::Sha256::default()),
+            HashScheme::Keccak256 => RunningHash::Keccak256(tiny_keccak::Keccak::v256()),
+        }
+    }
+
+    fn update(&mut self, bytes: &[u8]) {
+        match self {
+            RunningHash::Sha256(hasher) => {
+                use sha2::Digest;
+                hasher.update(bytes);
+            }
+            RunningHash::Keccak256(hasher) => {
+                use tiny_keccak::Hasher;
+                hasher.update(bytes);
+            }
+        }
+    }
+
+    // Clones the hasher before finalizing so the running state keeps accumulating.
+    fn digest(&self) -> [u8; 32] {
+        match self {
+            RunningHash::Sha256(hasher) => {
+                use sha2::Digest;
+                hasher.clone().finalize().into()
+            }
+            RunningHash::Keccak256(hasher) => {
+                use tiny_keccak::Hasher;
+                let mut clone = hasher.clone();
+                let mut output = [0u8; 32];
+                clone.finalize(&mut output);
+                output
+            }
+        }
+    }
+}
+
+// Per-caller usage metering, folded into a running hash of served requests.
+struct Billing {
+    hash: RunningHash,
+    costs: std::collections::HashMap<String, u64>,
+}
+
+impl Billing {
+    fn new(hash_scheme: HashScheme) -> Self {
+        Self {
+            hash: RunningHash::new(hash_scheme),
+            costs: std::collections::HashMap::new(),
+        }
+    }
+
+    /// Folds a served request into the running digest and per-caller cost total.
+    fn record(&mut self, caller_id: &str, nonce: u64, cost: u64) {
+        self.hash.update(caller_id.as_bytes());
+        self.hash.update(&nonce.to_le_bytes());
+        self.hash.update(&cost.to_le_bytes());
+
+        *self.costs.entry(caller_id.to_string()).or_insert(0) += cost;
+    }
+
+    /// Snapshots the current digest and per-caller costs without disturbing
+    /// the running hash, so metering keeps accumulating across `/bill` calls.
+    fn snapshot(&self) -> ([u8; 32], std::collections::HashMap<String, u64>) {
+        (self.hash.digest(), self.costs.clone())
+    }
+}
+
 // Application state
 struct AppState {
     signing_key: SecretKey,
-    http_client: reqwest::Client,
+    price_sources: AggregatedSource,
+    // Monotonic counter included in every signed payload; on-chain consumers
+    // reject updates with `nonce <= last_seen`.
+    nonce: std::sync::atomic::AtomicU64,
+    // Hands off persistence to `run_nonce_persist_task`, which runs the write
+    // via `spawn_blocking`. `None` if no `nonce_file` is configured.
+    nonce_persist_tx: Option<tokio::sync::mpsc::UnboundedSender<u64>>,
+    billing: std::sync::Mutex<Billing>,
+    hash_scheme: HashScheme,
+    // Maps an authenticated API key to the caller id billed for its usage.
+    api_keys: std::collections::HashMap<String, String>,
+}
+
+impl AppState {
+    /// Increments the nonce and hands the new value off to the persistence
+    /// task, if configured.
+    fn next_nonce(&self) -> u64 {
+        let nonce = self
+            .nonce
+            .fetch_add(1, std::sync::atomic::Ordering::SeqCst)
+            + 1;
+
+        if let Some(tx) = &self.nonce_persist_tx {
+            // Unbounded send never blocks; an error just means the
+            // persistence task has shut down, which is logged there.
+            let _ = tx.send(nonce);
+        }
+
+        nonce
+    }
+}
+
+/// Reads the last-persisted nonce from `path`, or `0` if the file doesn't
+/// exist yet (e.g. first boot).
+fn load_last_nonce<P: AsRef<Path>>(path: P) -> Result<u64, anyhow::Error> {
+    match fs::read_to_string(path) {
+        Ok(contents) => Ok(contents.trim().parse()?),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(0),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Loads the API key registry from a file of `api_key:caller_id` lines
+/// (blank lines and lines starting with `#` are skipped).
+fn load_api_keys<P: AsRef<Path>>(
+    path: P,
+) -> Result<std::collections::HashMap<String, String>, anyhow::Error> {
+    let contents = fs::read_to_string(path)?;
+
+    let mut api_keys = std::collections::HashMap::new();
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let (api_key, caller_id) = line
+            .split_once(':')
+            .ok_or_else(|| anyhow::anyhow!("Malformed API key line (expected 'key:caller_id'): {}", line))?;
+
+        api_keys.insert(api_key.trim().to_string(), caller_id.trim().to_string());
+    }
+
+    Ok(api_keys)
+}
+
+/// Persists each nonce received from `rx` to `path`, one at a time via
+/// `spawn_blocking` so the write never stalls a tokio worker thread.
+///
+/// Concurrent callers can race between their own `fetch_add` and channel
+/// `send`, so nonces can arrive out of issuance order; only writing a nonce
+/// that's larger than the last one actually persisted keeps the file
+/// monotonic regardless of arrival order.
+async fn run_nonce_persist_task(
+    mut rx: tokio::sync::mpsc::UnboundedReceiver<u64>,
+    path: std::path::PathBuf,
+) {
+    let mut last_persisted = 0u64;
+
+    while let Some(nonce) = rx.recv().await {
+        if nonce <= last_persisted {
+            continue;
+        }
+
+        let write_path = path.clone();
+        let result =
+            tokio::task::spawn_blocking(move || fs::write(&write_path, nonce.to_string())).await;
+
+        match result {
+            Ok(Err(e)) => {
+                tracing::warn!("Failed to persist nonce to {}: {}", path.display(), e);
+            }
+            Err(e) => {
+                tracing::warn!("Nonce persistence task panicked: {}", e);
+            }
+            Ok(Ok(())) => {
+                last_persisted = nonce;
+            }
+        }
+    }
 }
 
 // Intent scope constant (0 for personal intent)
 const INTENT_SCOPE: u8 = 0;
 
-async fn fetch_sui_price(http_client: &reqwest::Client) -> Result<f64, anyhow::Error> {
-    let url = "https://api.coingecko.com/api/v3/simple/price?ids=sui&vs_currencies=usd";
-    
-    let response = http_client
-        .get(url)
-        .send()
-        .await?
-        .json::<CoinGeckoResponse>()
-        .await?;
-    
-    Ok(response.sui.usd)
-}
+// Minimum number of independent feeds that must respond before we'll sign a price.
+const PRICE_QUORUM: usize = 2;
+
+// Cached feed values older than this when read are rejected; the HTTP feeds
+// fetch a fresh value per request and do not check it against this.
+const MAX_PRICE_STALENESS_MS: u64 = 5_000;
+
+// Flat cost charged to a caller for each served `/price` request.
+const COST_PER_PRICE_REQUEST: u64 = 1;
 
 fn current_timestamp_ms() -> u64 {
     SystemTime::now()
@@ -76,41 +577,74 @@ fn current_timestamp_ms() -> u64 {
         .as_millis() as u64
 }
 
-// Sign the price data following Nautilus pattern
+/// Signs a 32-byte digest with the recoverable secp256k1 scheme, returning
+/// the hex-encoded 65-byte signature (r||s||v) and the recovery id.
+fn sign_digest_recoverable(
+    signing_key: &SecretKey,
+    digest: &[u8; 32],
+) -> Result<(String, i32), anyhow::Error> {
+    let message = Message::from_digest_slice(digest)?;
+
+    let secp = Secp256k1::new();
+    let signature = secp.sign_ecdsa_recoverable(&message, signing_key);
+    let (recovery_id, compact) = signature.serialize_compact();
+
+    // 65 bytes: r||s||v, with v as the last byte
+    let mut bytes = Vec::with_capacity(65);
+    bytes.extend_from_slice(&compact);
+    bytes.push(recovery_id.to_i32() as u8);
+
+    Ok((hex::encode(bytes), recovery_id.to_i32()))
+}
+
+/// Hashes and signs the price data following the Nautilus pattern, using
+/// the prehash function selected by `hash_scheme`. When `recoverable` is
+/// set, the returned signature is 65 bytes (r||s||v) and the recovery id
+/// is returned alongside it; otherwise it's the existing 64-byte (r||s)
+/// compact form.
 fn sign_price_data(
     signing_key: &SecretKey,
     price: u64,
     timestamp_ms: u64,
-) -> Result<String, anyhow::Error> {
-    let payload = PriceUpdatePayload { price };
-    
+    nonce: u64,
+    recoverable: bool,
+    hash_scheme: HashScheme,
+) -> Result<(String, Option<i32>), anyhow::Error> {
+    let payload = PriceUpdatePayload { price, nonce };
+
     let intent_message = IntentMessage {
         intent: INTENT_SCOPE,
         timestamp_ms,
         data: payload,
     };
-    
+
     // BCS serialize the IntentMessage
     let message_bytes = bcs::to_bytes(&intent_message)?;
-    
-    // Hash the message with SHA256 (we'll use hash flag 1 in Sui)
-    use sha2::{Sha256, Digest};
-    let hash = Sha256::digest(&message_bytes);
-    let message = Message::from_digest_slice(&hash)?;
-    
-    // Sign with secp256k1
-    let secp = Secp256k1::new();
-    let signature = secp.sign_ecdsa(&message, signing_key);
-    
-    // Return hex-encoded signature (64 bytes: r + s)
-    Ok(hex::encode(signature.serialize_compact()))
+
+    let hash = hash_scheme.digest(&message_bytes);
+
+    if recoverable {
+        let (signature, recovery_id) = sign_digest_recoverable(signing_key, &hash)?;
+        Ok((signature, Some(recovery_id)))
+    } else {
+        let message = Message::from_digest_slice(&hash)?;
+        let secp = Secp256k1::new();
+        let signature = secp.sign_ecdsa(&message, signing_key);
+
+        // 64-byte signature: r + s
+        Ok((hex::encode(signature.serialize_compact()), None))
+    }
 }
 
 async fn get_signed_price(
     State(state): State<Arc<AppState>>,
+    Query(params): Query<GetPriceParams>,
+    headers: axum::http::HeaderMap,
 ) -> Result<Json<SignedPriceResponse>, (StatusCode, String)> {
-    // Fetch current SUI price from CoinGecko
-    let price_usd = fetch_sui_price(&state.http_client)
+    let caller_id = authenticate_caller(&state, &headers)?;
+
+    // Fetch current SUI price as the median across all configured feeds
+    let price_usd = state.price_sources.median_price()
         .await
         .map_err(|e| {
             tracing::error!("Failed to fetch SUI price: {}", e);
@@ -124,18 +658,59 @@ async fn get_signed_price(
     let timestamp_ms = current_timestamp_ms();
     
     info!("Fetched SUI price: ${:.6} (raw: {})", price_usd, price);
-    
+
+    // Every successful response consumes a fresh nonce, even if signing later
+    // fails, so a caller can never observe the same nonce signed twice.
+    let nonce = state.next_nonce();
+
     // Sign the data
-    let signature = sign_price_data(&state.signing_key, price, timestamp_ms)
-        .map_err(|e| {
-            tracing::error!("Failed to sign data: {}", e);
-            (StatusCode::INTERNAL_SERVER_ERROR, format!("Signing failed: {}", e))
-        })?;
-    
+    let (signature, recovery_id) = sign_price_data(
+        &state.signing_key,
+        price,
+        timestamp_ms,
+        nonce,
+        params.recoverable,
+        state.hash_scheme,
+    )
+    .map_err(|e| {
+        tracing::error!("Failed to sign data: {}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, format!("Signing failed: {}", e))
+    })?;
+
+    state
+        .billing
+        .lock()
+        .unwrap()
+        .record(&caller_id, nonce, COST_PER_PRICE_REQUEST);
+
     Ok(Json(SignedPriceResponse {
         price,
         timestamp_ms,
+        nonce,
+        signature,
+        recovery_id,
+        hash_scheme: state.hash_scheme,
+    }))
+}
+
+/// Finalizes a snapshot of the billing hash accumulated so far and signs it.
+async fn get_bill(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<BillResponse>, (StatusCode, String)> {
+    let (digest, costs) = state.billing.lock().unwrap().snapshot();
+
+    let (signature, recovery_id) = sign_digest_recoverable(&state.signing_key, &digest)
+        .map_err(|e| {
+            tracing::error!("Failed to sign billing hash: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, format!("Signing failed: {}", e))
+        })?;
+
+    Ok(Json(BillResponse {
+        costs,
+        hash: hex::encode(digest),
         signature,
+        recovery_id,
+        hash_scheme: state.hash_scheme,
     }))
 }
 
@@ -171,12 +746,26 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     
     // Get key path from command line args
     let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        eprintln!("Usage: {} <path-to-secp256k1-key>", args[0]);
+    if args.len() < 2 || args.len() > 3 {
+        eprintln!(
+            "Usage: {} <path-to-secp256k1-key> [--hash-scheme=sha256|keccak256]",
+            args[0]
+        );
         std::process::exit(1);
     }
     let key_path = &args[1];
-    
+
+    // The hash scheme can come from a CLI flag or the HASH_SCHEME env var
+    // (flag takes precedence); defaults to SHA-256 for Sui Move consumers.
+    let hash_scheme = match args.get(2).and_then(|arg| arg.strip_prefix("--hash-scheme=")) {
+        Some(value) => value.parse::<HashScheme>()?,
+        None => match env::var("HASH_SCHEME") {
+            Ok(value) => value.parse::<HashScheme>()?,
+            Err(_) => HashScheme::Sha256,
+        },
+    };
+    info!("Using hash scheme: {:?}", hash_scheme);
+
     info!("Loading secp256k1 signing key from: {}", key_path);
     let signing_key = load_signing_key_from_file(key_path)?;
     info!("Signing key loaded successfully");
@@ -187,16 +776,63 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     info!("Public key (hex): {}", hex::encode(public_key.serialize()));
     
     // Create shared state
+    let http_client = reqwest::Client::new();
+
+    let kraken_ticker_cache: KrakenTickerCache = Arc::new(tokio::sync::RwLock::new(None));
+    tokio::spawn(run_kraken_ticker_task(kraken_ticker_cache.clone()));
+
+    let price_sources = AggregatedSource::new(
+        vec![
+            Box::new(CoinGeckoSource {
+                http_client: http_client.clone(),
+            }),
+            Box::new(KrakenSource {
+                cache: kraken_ticker_cache,
+            }),
+            Box::new(BinanceSource {
+                http_client: http_client.clone(),
+            }),
+        ],
+        PRICE_QUORUM,
+        MAX_PRICE_STALENESS_MS,
+    );
+    // Optional: persist the nonce across restarts so replay protection
+    // doesn't reset to 0 every time the enclave restarts.
+    let nonce_file = env::var("NONCE_FILE_PATH").ok().map(std::path::PathBuf::from);
+    let last_nonce = match &nonce_file {
+        Some(path) => load_last_nonce(path)?,
+        None => 0,
+    };
+    info!("Starting nonce counter at {}", last_nonce);
+
+    let nonce_persist_tx = nonce_file.map(|path| {
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        tokio::spawn(run_nonce_persist_task(rx, path));
+        tx
+    });
+
+    // Billable callers are fixed at startup from a registry file.
+    let api_keys_path = env::var("API_KEYS_FILE_PATH")
+        .map_err(|_| anyhow::anyhow!("API_KEYS_FILE_PATH must be set to the API key registry"))?;
+    let api_keys = load_api_keys(&api_keys_path)?;
+    info!("Loaded {} API key(s) from {}", api_keys.len(), api_keys_path);
+
     let state = Arc::new(AppState {
         signing_key,
-        http_client: reqwest::Client::new(),
+        price_sources,
+        nonce: std::sync::atomic::AtomicU64::new(last_nonce),
+        nonce_persist_tx,
+        billing: std::sync::Mutex::new(Billing::new(hash_scheme)),
+        hash_scheme,
+        api_keys,
     });
-    
+
     // Build router
     let app = Router::new()
         .route("/health", get(health_check))
         .route("/price", get(get_signed_price))
         .route("/public-key", get(get_public_key))
+        .route("/bill", get(get_bill))
         .with_state(state);
     
     // Start server